@@ -8,27 +8,99 @@
 //! optimization algorithm but there is flexibility to introduce new
 //! algorithms and git them into the same scheme easily.
 
+use std::cmp;
+use std::collections::VecDeque;
+
+use rand::{Rng, SeedableRng, StdRng};
+
 use learning::optim::{Optimizable, OptimAlgorithm};
 use linalg::vector::Vector;
 use linalg::matrix::Matrix;
 
+/// A regularization penalty applied to the model parameters during an
+/// optimizer's update step.
+///
+/// The penalty contributes an additional sub-gradient term which is
+/// added to the model's gradient before the parameter update, so that
+/// `compute_grad` itself never needs to know about regularization.
+pub enum Penalty {
+    /// No penalty is applied.
+    None,
+    /// L1 (lasso) penalty with the given regularization strength.
+    L1(f64),
+    /// L2 (ridge) penalty with the given regularization strength.
+    L2(f64),
+    /// Elastic-net penalty combining an L1 and an L2 regularization
+    /// strength.
+    ElasticNet {
+        /// The L1 regularization strength.
+        l1: f64,
+        /// The L2 regularization strength.
+        l2: f64,
+    },
+}
+
+impl Penalty {
+    /// Computes the penalty sub-gradient for a single parameter value.
+    fn grad(&self, theta: f64) -> f64 {
+        match *self {
+            Penalty::None => 0f64,
+            Penalty::L1(lambda) => lambda * sign(theta),
+            Penalty::L2(lambda) => lambda * theta,
+            Penalty::ElasticNet { l1, l2 } => l1 * sign(theta) + l2 * theta,
+        }
+    }
+
+    /// Adds this penalty's sub-gradient to `grad` in place, skipping
+    /// any parameter index present in `exclude` (typically a bias or
+    /// intercept term which should not be shrunk).
+    fn apply(&self, grad: &mut [f64], theta: &[f64], exclude: &[usize]) {
+        for i in 0..grad.len() {
+            if !exclude.contains(&i) {
+                grad[i] += self.grad(theta[i]);
+            }
+        }
+    }
+}
+
+/// Returns the sign of `x`, or `0` when `x` is exactly zero.
+fn sign(x: f64) -> f64 {
+    if x > 0f64 {
+        1f64
+    } else if x < 0f64 {
+        -1f64
+    } else {
+        0f64
+    }
+}
+
 /// Batch Gradient Descent algorithm
 pub struct GradientDesc {
     /// The step-size for the gradient descent steps.
     pub alpha: f64,
     /// The number of iterations to run.
     pub iters: usize,
+    /// The gradient norm below which the descent stops early.
+    pub tol: f64,
+    /// The regularization penalty applied at each step.
+    pub penalty: Penalty,
+    /// Parameter indices excluded from the penalty, e.g. a bias term.
+    pub penalty_exclude: Vec<usize>,
 }
 
 impl Default for GradientDesc {
     /// Constructs a gradient descent algorithm
     /// with default settings.
     ///
-    /// Uses 10000 iterations and step size of 0.3.
+    /// Uses 100 iterations, step size of 0.3, a tolerance of 1e-6
+    /// and no regularization penalty.
     fn default() -> GradientDesc {
         GradientDesc {
             alpha: 0.3,
             iters: 100,
+            tol: 1e-6,
+            penalty: Penalty::None,
+            penalty_exclude: Vec::new(),
         }
     }
 }
@@ -36,21 +108,62 @@ impl Default for GradientDesc {
 impl GradientDesc {
     /// Construct a gradient descent algorithm.
     ///
-    /// Requires the step size and iteration count
-    /// to be specified.
+    /// Requires the step size, iteration count, the gradient norm
+    /// tolerance used for early stopping, the regularization penalty
+    /// and the parameter indices excluded from it to be specified.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rusty_machine::learning::optim::grad_desc::GradientDesc;
+    /// use rusty_machine::learning::optim::grad_desc::{GradientDesc, Penalty};
     ///
-    /// let gd = GradientDesc::new(0.3, 10000);
+    /// let gd = GradientDesc::new(0.3, 10000, 1e-6, Penalty::L2(0.1), vec![0]);
     /// ```
-    pub fn new(alpha: f64, iters: usize) -> GradientDesc {
+    pub fn new(alpha: f64,
+               iters: usize,
+               tol: f64,
+               penalty: Penalty,
+               penalty_exclude: Vec<usize>)
+               -> GradientDesc {
         GradientDesc {
             alpha: alpha,
             iters: iters,
+            tol: tol,
+            penalty: penalty,
+            penalty_exclude: penalty_exclude,
+        }
+    }
+
+    /// Runs the same descent as `optimize` but additionally returns
+    /// the gradient-norm trace recorded at each iteration, stopping
+    /// early once that norm falls below `self.tol`.
+    ///
+    /// The returned trace allows callers to detect non-convergence or
+    /// to plot a learning curve.
+    pub fn optimize_with_log<M: Optimizable>(&self,
+                                              model: &M,
+                                              start: &[f64],
+                                              data: &M::Data,
+                                              outputs: &M::Target)
+                                              -> (Vec<f64>, Vec<f64>) {
+        let mut optimizing_val = Vector::new(start.to_vec());
+        let mut grad_norms = Vec::with_capacity(self.iters);
+
+        for _ in 0..self.iters {
+            let mut grad = model.compute_grad(&optimizing_val.data()[..], data, outputs).1;
+            self.penalty.apply(&mut grad, optimizing_val.data(), &self.penalty_exclude);
+
+            let grad = Vector::new(grad);
+            let grad_norm = grad.norm();
+            grad_norms.push(grad_norm);
+
+            if grad_norm < self.tol {
+                break;
+            }
+
+            optimizing_val = &optimizing_val - grad * self.alpha;
         }
+        (optimizing_val.into_vec(), grad_norms)
     }
 }
 
@@ -60,11 +173,15 @@ impl<M: Optimizable> OptimAlgorithm<M> for GradientDesc {
         let mut optimizing_val = Vector::new(start.to_vec());
 
         for _ in 0..self.iters {
-            optimizing_val = &optimizing_val -
-                             Vector::new(model.compute_grad(&optimizing_val.data()[..],
-                                                            data,
-                                                            outputs)
-                                              .1) * self.alpha;
+            let mut grad = model.compute_grad(&optimizing_val.data()[..], data, outputs).1;
+            self.penalty.apply(&mut grad, optimizing_val.data(), &self.penalty_exclude);
+
+            let grad = Vector::new(grad);
+            if grad.norm() < self.tol {
+                break;
+            }
+
+            optimizing_val = &optimizing_val - grad * self.alpha;
         }
         optimizing_val.into_vec()
     }
@@ -80,18 +197,33 @@ pub struct StochasticGD {
     pub mu: f64,
     /// The number of passes through the data.
     pub iters: usize,
+    /// The `delta_w` norm below which the descent stops early.
+    pub tol: f64,
+    /// The regularization penalty applied at each step.
+    pub penalty: Penalty,
+    /// Parameter indices excluded from the penalty, e.g. a bias term.
+    pub penalty_exclude: Vec<usize>,
+    /// Whether to use Nesterov's accelerated gradient, which evaluates
+    /// the gradient at the momentum look-ahead point `theta - alpha *
+    /// delta_w` rather than at `theta` itself.
+    pub nesterov: bool,
 }
 
 impl Default for StochasticGD {
     /// Constructs a stochastic gradient descent algorithm
     /// with default settings.
     ///
-    /// Uses 5 iterations, momentum of 0.1 and rate of 0.3.
+    /// Uses 20 iterations, momentum of 0.1, rate of 0.1, a tolerance of
+    /// 1e-6, no regularization penalty and no Nesterov acceleration.
     fn default() -> StochasticGD {
         StochasticGD {
             alpha: 0.1,
             mu: 0.1,
             iters: 20,
+            tol: 1e-6,
+            penalty: Penalty::None,
+            penalty_exclude: Vec::new(),
+            nesterov: false,
         }
     }
 }
@@ -99,45 +231,674 @@ impl Default for StochasticGD {
 impl StochasticGD {
     /// Construct a stochastic gradient descent algorithm.
     ///
-    /// Requires the learning rate, momentum rate and iteration count
-    /// to be specified.
+    /// Requires the momentum rate, learning rate, iteration count, the
+    /// `delta_w` norm tolerance used for early stopping, the
+    /// regularization penalty, the parameter indices excluded from it
+    /// and whether to use Nesterov acceleration to be specified.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rusty_machine::learning::optim::grad_desc::StochasticGD;
+    /// use rusty_machine::learning::optim::grad_desc::{StochasticGD, Penalty};
     ///
-    /// let sgd = StochasticGD::new(0.1, 0.3, 5);
+    /// let sgd = StochasticGD::new(0.1, 0.3, 5, 1e-6, Penalty::L2(0.1), vec![0], true);
     /// ```
-    pub fn new(alpha: f64, mu: f64, iters: usize) -> StochasticGD {
+    pub fn new(alpha: f64,
+               mu: f64,
+               iters: usize,
+               tol: f64,
+               penalty: Penalty,
+               penalty_exclude: Vec<usize>,
+               nesterov: bool)
+               -> StochasticGD {
         StochasticGD {
             alpha: alpha,
             mu: mu,
             iters: iters,
+            tol: tol,
+            penalty: penalty,
+            penalty_exclude: penalty_exclude,
+            nesterov: nesterov,
+        }
+    }
+
+    /// The point at which the gradient should be evaluated for the
+    /// next row: the look-ahead point `theta - alpha * delta_w` under
+    /// Nesterov acceleration, or `theta` itself otherwise.
+    fn grad_point(&self, optimizing_val: &Vector<f64>, delta_w: &Vector<f64>) -> Vector<f64> {
+        if self.nesterov {
+            optimizing_val - delta_w * self.alpha
+        } else {
+            optimizing_val.clone()
+        }
+    }
+
+    /// Runs the same descent as `optimize` but additionally returns
+    /// the `delta_w`-norm trace recorded at each row update, stopping
+    /// early once that norm falls below `self.tol`.
+    ///
+    /// The returned trace allows callers to detect non-convergence or
+    /// to plot a learning curve.
+    pub fn optimize_with_log<M: Optimizable<Data = Matrix<f64>, Target = Matrix<f64>>>
+        (&self,
+         model: &M,
+         start: &[f64],
+         data: &M::Data,
+         outputs: &M::Target)
+         -> (Vec<f64>, Vec<f64>) {
+
+        let (_, mut vec_data) = model.compute_grad(start,
+                                                   &data.select_rows(&[0]),
+                                                   &outputs.select_rows(&[0]));
+        self.penalty.apply(&mut vec_data, start, &self.penalty_exclude);
+        let grad = Vector::new(vec_data);
+        let mut delta_w = grad * self.alpha;
+        let mut optimizing_val = Vector::new(start.to_vec()) - &delta_w * self.mu;
+        let mut delta_norms = Vec::with_capacity(self.iters * data.rows());
+
+        'outer: for _ in 0..self.iters {
+            for i in 1..data.rows() {
+                let grad_point = self.grad_point(&optimizing_val, &delta_w);
+                let (_, mut vec_data) = model.compute_grad(&grad_point.data()[..],
+                                                           &data.select_rows(&[i]),
+                                                           &outputs.select_rows(&[i]));
+                self.penalty.apply(&mut vec_data, grad_point.data(), &self.penalty_exclude);
+
+                delta_w = Vector::new(vec_data) * self.mu + &delta_w * self.alpha;
+                let delta_norm = delta_w.norm();
+                delta_norms.push(delta_norm);
+
+                if delta_norm < self.tol {
+                    break 'outer;
+                }
+
+                optimizing_val = &optimizing_val - &delta_w * self.mu;
+            }
         }
+        (optimizing_val.into_vec(), delta_norms)
     }
 }
 
 impl<M: Optimizable<Data = Matrix<f64>, Target = Matrix<f64>>> OptimAlgorithm<M> for StochasticGD {
     fn optimize(&self, model: &M, start: &[f64], data: &M::Data, outputs: &M::Target) -> Vec<f64> {
 
-        let (_, vec_data) = model.compute_grad(start,
-                                               &data.select_rows(&[0]),
-                                               &outputs.select_rows(&[0]));
+        let (_, mut vec_data) = model.compute_grad(start,
+                                                   &data.select_rows(&[0]),
+                                                   &outputs.select_rows(&[0]));
+        self.penalty.apply(&mut vec_data, start, &self.penalty_exclude);
         let grad = Vector::new(vec_data);
         let mut delta_w = grad * self.alpha;
         let mut optimizing_val = Vector::new(start.to_vec()) - &delta_w * self.mu;
 
-        for _ in 0..self.iters {
+        'outer: for _ in 0..self.iters {
             for i in 1..data.rows() {
-                let (_, vec_data) = model.compute_grad(&optimizing_val.data()[..],
-                                                       &data.select_rows(&[i]),
-                                                       &outputs.select_rows(&[i]));
+                let grad_point = self.grad_point(&optimizing_val, &delta_w);
+                let (_, mut vec_data) = model.compute_grad(&grad_point.data()[..],
+                                                           &data.select_rows(&[i]),
+                                                           &outputs.select_rows(&[i]));
+                self.penalty.apply(&mut vec_data, grad_point.data(), &self.penalty_exclude);
 
                 delta_w = Vector::new(vec_data) * self.mu + &delta_w * self.alpha;
+
+                if delta_w.norm() < self.tol {
+                    break 'outer;
+                }
+
                 optimizing_val = &optimizing_val - &delta_w * self.mu;
             }
         }
         optimizing_val.into_vec()
     }
 }
+
+/// Adam adaptive-moment optimizer.
+///
+/// Maintains a per-parameter exponential moving average of the
+/// gradient (the first moment) and of the squared gradient (the
+/// second moment) and uses bias-corrected estimates of both to scale
+/// the step taken for each parameter individually.
+pub struct Adam {
+    /// The step-size for the adam update.
+    pub alpha: f64,
+    /// The exponential decay rate for the first moment estimate.
+    pub beta1: f64,
+    /// The exponential decay rate for the second moment estimate.
+    pub beta2: f64,
+    /// A small constant used to avoid division by zero.
+    pub epsilon: f64,
+    /// The number of iterations to run.
+    pub iters: usize,
+}
+
+impl Default for Adam {
+    /// Constructs an Adam optimizer with default settings.
+    ///
+    /// Uses 100 iterations, step size of 0.001, beta1 of 0.9,
+    /// beta2 of 0.999 and epsilon of 1e-8.
+    fn default() -> Adam {
+        Adam {
+            alpha: 0.001,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            iters: 100,
+        }
+    }
+}
+
+impl Adam {
+    /// Construct an Adam optimizer.
+    ///
+    /// Requires the step size, the two moment decay rates, the
+    /// numerical stability constant and the iteration count to be
+    /// specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::Adam;
+    ///
+    /// let adam = Adam::new(0.001, 0.9, 0.999, 1e-8, 100);
+    /// ```
+    pub fn new(alpha: f64, beta1: f64, beta2: f64, epsilon: f64, iters: usize) -> Adam {
+        Adam {
+            alpha: alpha,
+            beta1: beta1,
+            beta2: beta2,
+            epsilon: epsilon,
+            iters: iters,
+        }
+    }
+}
+
+impl<M: Optimizable> OptimAlgorithm<M> for Adam {
+    fn optimize(&self, model: &M, start: &[f64], data: &M::Data, outputs: &M::Target) -> Vec<f64> {
+
+        let mut optimizing_val = start.to_vec();
+        let mut m = vec![0f64; start.len()];
+        let mut v = vec![0f64; start.len()];
+
+        for t in 1..(self.iters + 1) {
+            let (_, grad) = model.compute_grad(&optimizing_val[..], data, outputs);
+
+            let bias_correction1 = 1f64 - self.beta1.powi(t as i32);
+            let bias_correction2 = 1f64 - self.beta2.powi(t as i32);
+
+            for i in 0..optimizing_val.len() {
+                m[i] = self.beta1 * m[i] + (1f64 - self.beta1) * grad[i];
+                v[i] = self.beta2 * v[i] + (1f64 - self.beta2) * grad[i] * grad[i];
+
+                let m_hat = m[i] / bias_correction1;
+                let v_hat = v[i] / bias_correction2;
+
+                optimizing_val[i] -= self.alpha * m_hat / (v_hat.sqrt() + self.epsilon);
+            }
+        }
+        optimizing_val
+    }
+}
+
+/// L-BFGS quasi-Newton optimizer.
+///
+/// Approximates the inverse Hessian from a limited history of the
+/// last `history` parameter and gradient differences, giving
+/// second-order-like convergence without storing a full Hessian.
+pub struct LBFGS {
+    /// The step-size for the line-search-free update.
+    pub alpha: f64,
+    /// The number of previous steps to retain for the curvature
+    /// approximation.
+    pub history: usize,
+    /// The number of iterations to run.
+    pub iters: usize,
+    /// The gradient norm below which the descent stops early.
+    pub tol: f64,
+}
+
+impl Default for LBFGS {
+    /// Constructs an L-BFGS optimizer with default settings.
+    ///
+    /// Uses 100 iterations, a history size of 10, step size of 0.3
+    /// and a tolerance of 1e-6.
+    fn default() -> LBFGS {
+        LBFGS {
+            alpha: 0.3,
+            history: 10,
+            iters: 100,
+            tol: 1e-6,
+        }
+    }
+}
+
+impl LBFGS {
+    /// Construct an L-BFGS optimizer.
+    ///
+    /// Requires the step size, the history size, the iteration count
+    /// and the gradient norm tolerance used for early stopping to be
+    /// specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::LBFGS;
+    ///
+    /// let lbfgs = LBFGS::new(0.3, 10, 100, 1e-6);
+    /// ```
+    pub fn new(alpha: f64, history: usize, iters: usize, tol: f64) -> LBFGS {
+        LBFGS {
+            alpha: alpha,
+            history: history,
+            iters: iters,
+            tol: tol,
+        }
+    }
+}
+
+impl<M: Optimizable> OptimAlgorithm<M> for LBFGS {
+    fn optimize(&self, model: &M, start: &[f64], data: &M::Data, outputs: &M::Target) -> Vec<f64> {
+
+        let mut optimizing_val = start.to_vec();
+        let (_, mut grad) = model.compute_grad(&optimizing_val[..], data, outputs);
+
+        let mut s_hist: VecDeque<Vec<f64>> = VecDeque::with_capacity(self.history);
+        let mut y_hist: VecDeque<Vec<f64>> = VecDeque::with_capacity(self.history);
+        let mut rho_hist: VecDeque<f64> = VecDeque::with_capacity(self.history);
+
+        for _ in 0..self.iters {
+            if dot(&grad, &grad).sqrt() < self.tol {
+                break;
+            }
+
+            // Two-loop recursion computing the approximate search
+            // direction `q` from the stored curvature pairs.
+            let mut q = grad.clone();
+            let mut alphas = Vec::with_capacity(s_hist.len());
+
+            for ((s_k, y_k), rho_k) in s_hist.iter().zip(y_hist.iter()).zip(rho_hist.iter()).rev() {
+                let a_k = rho_k * dot(s_k, &q);
+                for i in 0..q.len() {
+                    q[i] -= a_k * y_k[i];
+                }
+                alphas.push(a_k);
+            }
+            alphas.reverse();
+
+            // Scale the initial inverse-Hessian approximation `H0` by
+            // the curvature of the most recent correction pair so the
+            // fixed step size `alpha` is calibrated to the true
+            // curvature rather than assuming `H0 = I`.
+            if let (Some(s_last), Some(y_last)) = (s_hist.back(), y_hist.back()) {
+                let gamma = dot(s_last, y_last) / dot(y_last, y_last);
+                for q_i in q.iter_mut() {
+                    *q_i *= gamma;
+                }
+            }
+
+            for ((s_k, y_k), (rho_k, a_k)) in s_hist.iter()
+                                                     .zip(y_hist.iter())
+                                                     .zip(rho_hist.iter().zip(alphas.iter())) {
+                let b_k = rho_k * dot(y_k, &q);
+                for i in 0..q.len() {
+                    q[i] += s_k[i] * (a_k - b_k);
+                }
+            }
+
+            let new_val: Vec<f64> = optimizing_val.iter()
+                                                   .zip(q.iter())
+                                                   .map(|(x, d)| x - self.alpha * d)
+                                                   .collect();
+            let (_, new_grad) = model.compute_grad(&new_val[..], data, outputs);
+
+            let s_k: Vec<f64> = new_val.iter().zip(optimizing_val.iter()).map(|(n, o)| n - o).collect();
+            let y_k: Vec<f64> = new_grad.iter().zip(grad.iter()).map(|(n, o)| n - o).collect();
+            let sy = dot(&s_k, &y_k);
+
+            // Skip the curvature update when `y_k . s_k` is not
+            // positive, which would make `rho_k` ill-conditioned and
+            // break the positive-definiteness of the approximation,
+            // but still take the step.
+            // `history == 0` means no curvature pairs are kept at all;
+            // any other size evicts the oldest pair once the buffers
+            // are full, so this never grows past `self.history`.
+            if sy > 1e-10 && self.history > 0 {
+                if s_hist.len() >= self.history {
+                    s_hist.pop_front();
+                    y_hist.pop_front();
+                    rho_hist.pop_front();
+                }
+                s_hist.push_back(s_k);
+                y_hist.push_back(y_k);
+                rho_hist.push_back(1f64 / sy);
+            }
+
+            optimizing_val = new_val;
+            grad = new_grad;
+        }
+        optimizing_val
+    }
+}
+
+/// Computes the dot product of two equal-length slices.
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// Mini-batch Gradient Descent algorithm.
+///
+/// Each epoch shuffles the row indices with a seeded RNG and feeds
+/// contiguous chunks of that permutation to the model, averaging the
+/// gradient over the batch before applying the momentum update. This
+/// gives a tunable speed/variance tradeoff between the full-batch
+/// `GradientDesc` and the single-row `StochasticGD`.
+pub struct MiniBatchGD {
+    /// The learning rate applied to the averaged batch gradient.
+    pub alpha: f64,
+    /// The momentum decay rate applied to the previous `delta_w`.
+    pub mu: f64,
+    /// The number of rows in each mini-batch.
+    ///
+    /// Treated as `1` if set to `0`, since a zero-sized chunk is not
+    /// a valid batch.
+    pub batch_size: usize,
+    /// The number of passes through the data.
+    pub epochs: usize,
+    /// The seed used to shuffle the row indices each epoch.
+    pub seed: u32,
+}
+
+impl Default for MiniBatchGD {
+    /// Constructs a mini-batch gradient descent algorithm
+    /// with default settings.
+    ///
+    /// Uses 5 epochs, a learning rate of 0.1, momentum of 0.1, a
+    /// batch size of 10 and a seed of 0.
+    fn default() -> MiniBatchGD {
+        MiniBatchGD {
+            alpha: 0.1,
+            mu: 0.1,
+            batch_size: 10,
+            epochs: 5,
+            seed: 0,
+        }
+    }
+}
+
+impl MiniBatchGD {
+    /// Construct a mini-batch gradient descent algorithm.
+    ///
+    /// Requires the learning rate, momentum rate, batch size, epoch
+    /// count and RNG seed to be specified. A `batch_size` of `0` is
+    /// clamped up to `1`, since a zero-sized chunk is not a valid
+    /// batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::MiniBatchGD;
+    ///
+    /// let mbgd = MiniBatchGD::new(0.1, 0.3, 10, 5, 0);
+    /// ```
+    pub fn new(alpha: f64, mu: f64, batch_size: usize, epochs: usize, seed: u32) -> MiniBatchGD {
+        MiniBatchGD {
+            alpha: alpha,
+            mu: mu,
+            batch_size: cmp::max(batch_size, 1),
+            epochs: epochs,
+            seed: seed,
+        }
+    }
+}
+
+impl<M: Optimizable<Data = Matrix<f64>, Target = Matrix<f64>>> OptimAlgorithm<M> for MiniBatchGD {
+    fn optimize(&self, model: &M, start: &[f64], data: &M::Data, outputs: &M::Target) -> Vec<f64> {
+
+        let batch_size = cmp::max(self.batch_size, 1);
+        let mut rng: StdRng = SeedableRng::from_seed(&[self.seed as usize][..]);
+        let mut delta_w = Vector::new(vec![0f64; start.len()]);
+        let mut optimizing_val = Vector::new(start.to_vec());
+
+        for _ in 0..self.epochs {
+            let mut indices: Vec<usize> = (0..data.rows()).collect();
+            rng.shuffle(&mut indices);
+
+            for batch in indices.chunks(batch_size) {
+                let (_, vec_data) = model.compute_grad(&optimizing_val.data()[..],
+                                                       &data.select_rows(batch),
+                                                       &outputs.select_rows(batch));
+
+                let batch_len = batch.len() as f64;
+                let grad = Vector::new(vec_data.into_iter().map(|g| g / batch_len).collect::<Vec<_>>());
+
+                delta_w = grad * self.alpha + &delta_w * self.mu;
+                optimizing_val = &optimizing_val - &delta_w;
+            }
+        }
+        optimizing_val.into_vec()
+    }
+}
+
+/// RMSProp adaptive learning-rate optimizer.
+///
+/// Keeps a running mean-square of the gradient to scale each
+/// parameter's step individually, damping the update along
+/// high-curvature directions and boosting it along flat ones.
+pub struct RMSProp {
+    /// The step-size for the RMSProp update.
+    pub alpha: f64,
+    /// The decay rate of the running mean-square average.
+    pub gamma: f64,
+    /// A small constant used to avoid division by zero.
+    pub epsilon: f64,
+    /// The number of iterations to run.
+    pub iters: usize,
+}
+
+impl Default for RMSProp {
+    /// Constructs an RMSProp optimizer with default settings.
+    ///
+    /// Uses 100 iterations, step size of 0.001, gamma of 0.9 and
+    /// epsilon of 1e-8.
+    fn default() -> RMSProp {
+        RMSProp {
+            alpha: 0.001,
+            gamma: 0.9,
+            epsilon: 1e-8,
+            iters: 100,
+        }
+    }
+}
+
+impl RMSProp {
+    /// Construct an RMSProp optimizer.
+    ///
+    /// Requires the step size, the mean-square decay rate, the
+    /// numerical stability constant and the iteration count to be
+    /// specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::RMSProp;
+    ///
+    /// let rmsprop = RMSProp::new(0.001, 0.9, 1e-8, 100);
+    /// ```
+    pub fn new(alpha: f64, gamma: f64, epsilon: f64, iters: usize) -> RMSProp {
+        RMSProp {
+            alpha: alpha,
+            gamma: gamma,
+            epsilon: epsilon,
+            iters: iters,
+        }
+    }
+}
+
+impl<M: Optimizable> OptimAlgorithm<M> for RMSProp {
+    fn optimize(&self, model: &M, start: &[f64], data: &M::Data, outputs: &M::Target) -> Vec<f64> {
+
+        let mut optimizing_val = start.to_vec();
+        let mut ms = vec![0f64; start.len()];
+
+        for _ in 0..self.iters {
+            let (_, grad) = model.compute_grad(&optimizing_val[..], data, outputs);
+
+            for i in 0..optimizing_val.len() {
+                ms[i] = self.gamma * ms[i] + (1f64 - self.gamma) * grad[i] * grad[i];
+                optimizing_val[i] -= self.alpha * grad[i] / (ms[i].sqrt() + self.epsilon);
+            }
+        }
+        optimizing_val
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A toy model whose parameters are optimized directly against the
+    /// quadratic bowl `f(theta) = 0.5 * sum(theta_i^2)`, whose unique
+    /// minimum is the origin and whose gradient is `theta` itself.
+    struct Quadratic;
+
+    impl Optimizable for Quadratic {
+        type Data = ();
+        type Target = ();
+
+        fn compute_grad(&self, params: &[f64], _data: &(), _target: &()) -> (f64, Vec<f64>) {
+            let cost = params.iter().map(|p| 0.5 * p * p).sum();
+            (cost, params.to_vec())
+        }
+    }
+
+    /// Same quadratic bowl as `Quadratic`, but keyed to `Matrix<f64>`
+    /// data/targets so it can drive the row-based optimizers.
+    struct RowQuadratic;
+
+    impl Optimizable for RowQuadratic {
+        type Data = Matrix<f64>;
+        type Target = Matrix<f64>;
+
+        fn compute_grad(&self,
+                        params: &[f64],
+                        _data: &Matrix<f64>,
+                        _target: &Matrix<f64>)
+                        -> (f64, Vec<f64>) {
+            let cost = params.iter().map(|p| 0.5 * p * p).sum();
+            (cost, params.to_vec())
+        }
+    }
+
+    #[test]
+    fn adam_converges_on_quadratic() {
+        let adam = Adam { alpha: 0.1, iters: 1000, ..Adam::default() };
+        let result = adam.optimize(&Quadratic, &[10.0, -5.0], &(), &());
+
+        for x in result {
+            assert!(x.abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn lbfgs_converges_on_quadratic() {
+        let lbfgs = LBFGS { iters: 100, ..LBFGS::default() };
+        let result = lbfgs.optimize(&Quadratic, &[10.0, -5.0], &(), &());
+
+        for x in result {
+            assert!(x.abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn lbfgs_with_zero_history_does_not_panic_and_converges() {
+        let lbfgs = LBFGS { history: 0, iters: 100, ..LBFGS::default() };
+        let result = lbfgs.optimize(&Quadratic, &[10.0, -5.0], &(), &());
+
+        for x in result {
+            assert!(x.abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn stochastic_gd_optimize_and_optimize_with_log_agree() {
+        let data = Matrix::new(10, 1, vec![0.0; 10]);
+        let targets = Matrix::new(10, 1, vec![0.0; 10]);
+        let sgd = StochasticGD { iters: 10, ..StochasticGD::default() };
+
+        let via_optimize = sgd.optimize(&RowQuadratic, &[10.0], &data, &targets);
+        let (via_log, _) = sgd.optimize_with_log(&RowQuadratic, &[10.0], &data, &targets);
+
+        assert_eq!(via_optimize, via_log);
+    }
+
+    #[test]
+    fn mini_batch_gd_converges_on_quadratic() {
+        let data = Matrix::new(20, 1, vec![0.0; 20]);
+        let targets = Matrix::new(20, 1, vec![0.0; 20]);
+        let mbgd = MiniBatchGD { epochs: 20, batch_size: 4, ..MiniBatchGD::default() };
+
+        let result = mbgd.optimize(&RowQuadratic, &[10.0], &data, &targets);
+
+        assert!(result[0].abs() < 1.0);
+    }
+
+    #[test]
+    fn mini_batch_gd_with_zero_batch_size_does_not_panic() {
+        let data = Matrix::new(5, 1, vec![0.0; 5]);
+        let targets = Matrix::new(5, 1, vec![0.0; 5]);
+        let mbgd = MiniBatchGD { epochs: 1, batch_size: 0, ..MiniBatchGD::default() };
+
+        mbgd.optimize(&RowQuadratic, &[10.0], &data, &targets);
+    }
+
+    #[test]
+    fn penalty_sub_gradients_match_their_definitions() {
+        assert_eq!(Penalty::None.grad(3.0), 0.0);
+        assert_eq!(Penalty::L1(0.5).grad(3.0), 0.5);
+        assert_eq!(Penalty::L1(0.5).grad(-3.0), -0.5);
+        assert_eq!(Penalty::L2(0.5).grad(3.0), 1.5);
+        assert_eq!(Penalty::ElasticNet { l1: 0.5, l2: 0.2 }.grad(3.0), 0.5 + 0.2 * 3.0);
+    }
+
+    #[test]
+    fn penalty_excludes_given_indices() {
+        let mut grad = vec![1.0, 1.0, 1.0];
+        let theta = vec![2.0, 2.0, 2.0];
+        Penalty::L2(0.5).apply(&mut grad, &theta, &[1]);
+
+        assert_eq!(grad, vec![1.0 + 0.5 * 2.0, 1.0, 1.0 + 0.5 * 2.0]);
+    }
+
+    #[test]
+    fn gradient_desc_with_l2_penalty_converges_near_origin() {
+        let gd = GradientDesc {
+            iters: 500,
+            penalty: Penalty::L2(0.1),
+            ..GradientDesc::default()
+        };
+        let result = gd.optimize(&Quadratic, &[10.0, -5.0], &(), &());
+
+        for x in result {
+            assert!(x.abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn rmsprop_converges_on_quadratic() {
+        let rmsprop = RMSProp { alpha: 0.01, iters: 2000, ..RMSProp::default() };
+        let result = rmsprop.optimize(&Quadratic, &[10.0, -5.0], &(), &());
+
+        for x in result {
+            assert!(x.abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn stochastic_gd_with_nesterov_converges_on_quadratic() {
+        let data = Matrix::new(20, 1, vec![0.0; 20]);
+        let targets = Matrix::new(20, 1, vec![0.0; 20]);
+        let sgd = StochasticGD { iters: 20, nesterov: true, ..StochasticGD::default() };
+
+        let result = sgd.optimize(&RowQuadratic, &[10.0], &data, &targets);
+
+        assert!(result[0].abs() < 1.0);
+    }
+}